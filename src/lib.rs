@@ -1,5 +1,7 @@
 //! Actix middleware that computes strong ETags for responses and enforces
-//! conditional request semantics for `If-Match` and `If-None-Match` headers.
+//! conditional request semantics for `If-Match` and `If-None-Match` headers,
+//! falling back to `If-Modified-Since`/`Last-Modified` when no entity-tag
+//! condition is present.
 //!
 //! Wrap your Actix `App` with [`ETag`] to automatically add ETag headers to
 //! successful responses and to short-circuit requests when the client's cached
@@ -52,23 +54,56 @@
 
 use actix_web::{
     Error, HttpResponse,
-    body::{BoxBody, MessageBody, to_bytes},
+    body::{BodySize, BoxBody, MessageBody, to_bytes},
     dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
     http::{Method, StatusCode, header},
     web::Bytes,
 };
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use futures_util::future::{LocalBoxFuture, Ready, ok};
+use std::ops::RangeInclusive;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crc32fast::Hasher;
+use crc32fast::Hasher as Crc32Hasher;
+
+/// Digest algorithm used to compute the body hash backing an ETag value.
+///
+/// [`HashAlgorithm::Crc32`] is the default for backwards compatibility, but it
+/// is only a 32-bit checksum and carries a non-trivial collision rate on
+/// large corpora. Prefer [`HashAlgorithm::Xxh3`] or [`HashAlgorithm::Blake3`]
+/// when correctness under collisions matters more than raw speed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    /// 32-bit CRC checksum (the historical default).
+    #[default]
+    Crc32,
+    /// 128-bit xxh3 hash; strong collision resistance at high speed.
+    Xxh3,
+    /// 256-bit BLAKE3 hash for cryptographic-grade uniqueness.
+    Blake3,
+}
+
+/// User-supplied hook that may produce an entity-tag directly from a cheap,
+/// already-known version identifier instead of hashing the body. See
+/// [`ETag::compute_etag_with`].
+type EtagHook = Rc<dyn Fn(&actix_web::HttpRequest, &HttpResponse<()>, &Bytes) -> Option<String>>;
 
 /// Middleware that injects ETag headers and evaluates conditional requests.
 ///
 /// Use [`ETag::strong`] (default) or [`ETag::weak`] depending on whether your
-/// handlers should produce strong or weak validators.
-#[derive(Clone, Copy)]
+/// handlers should produce strong or weak validators, and [`ETag::with_hasher`]
+/// to pick the digest backend. By default only safe/idempotent methods
+/// (`GET`, `HEAD`) and 2xx responses are eligible; use [`ETag::methods`] and
+/// [`ETag::statuses`] to widen or narrow that set.
+#[derive(Clone)]
 pub struct ETag {
     strength: Strength,
+    hasher: HashAlgorithm,
+    max_body_size: Option<usize>,
+    methods: Vec<Method>,
+    statuses: RangeInclusive<u16>,
+    compute_etag: Option<EtagHook>,
 }
 
 #[derive(Clone, Copy)]
@@ -79,24 +114,91 @@ enum Strength {
 
 impl ETag {
     /// Constructs middleware using the default strong ETag strategy.
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self::strong()
     }
 
     /// Constructs middleware that emits strong ETags (the default behaviour).
-    pub const fn strong() -> Self {
+    pub fn strong() -> Self {
         Self {
             strength: Strength::Strong,
+            hasher: HashAlgorithm::Crc32,
+            max_body_size: None,
+            methods: default_methods(),
+            statuses: default_statuses(),
+            compute_etag: None,
         }
     }
 
     /// Constructs middleware that emits weak ETags while still honouring
     /// conditional request handling.
-    pub const fn weak() -> Self {
+    pub fn weak() -> Self {
         Self {
             strength: Strength::Weak,
+            hasher: HashAlgorithm::Crc32,
+            max_body_size: None,
+            methods: default_methods(),
+            statuses: default_statuses(),
+            compute_etag: None,
         }
     }
+
+    /// Selects the digest backend used to compute entity tags. Defaults to
+    /// [`HashAlgorithm::Crc32`] so existing callers are unaffected.
+    pub const fn with_hasher(mut self, hasher: HashAlgorithm) -> Self {
+        self.hasher = hasher;
+        self
+    }
+
+    /// Caps the response body size eligible for ETag computation. Bodies whose
+    /// size cannot be determined up front (e.g. chunked streams) or that
+    /// exceed `limit` bytes are forwarded untouched, without being buffered
+    /// into memory, and receive no ETag header or conditional evaluation.
+    /// Unset by default, which preserves the historical behaviour of always
+    /// buffering the body.
+    pub const fn max_body_size(mut self, limit: usize) -> Self {
+        self.max_body_size = Some(limit);
+        self
+    }
+
+    /// Restricts ETag generation and conditional evaluation to the given
+    /// request methods. Defaults to `GET` and `HEAD`. Requests using any
+    /// other method are forwarded untouched.
+    pub fn methods<I: IntoIterator<Item = Method>>(mut self, methods: I) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Restricts ETag generation and conditional evaluation to responses
+    /// whose status code falls within `statuses`. Defaults to `200..=299`.
+    /// Responses outside the range are forwarded untouched.
+    pub fn statuses(mut self, statuses: RangeInclusive<u16>) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
+    /// Supplies a hook that can produce an entity-tag from an already-known
+    /// version identifier (a DB row version, an object generation, an
+    /// upstream content hash) instead of hashing the body. The hook runs
+    /// before the body is hashed; if it returns `None`, computation falls
+    /// back to [`HashAlgorithm`]-based hashing as usual. The returned value
+    /// is wrapped as a proper entity-tag (quoted, and `W/`-prefixed for weak
+    /// ETags) unless it already looks like one.
+    pub fn compute_etag_with<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&actix_web::HttpRequest, &HttpResponse<()>, &Bytes) -> Option<String> + 'static,
+    {
+        self.compute_etag = Some(Rc::new(hook));
+        self
+    }
+}
+
+fn default_methods() -> Vec<Method> {
+    vec![Method::GET, Method::HEAD]
+}
+
+fn default_statuses() -> RangeInclusive<u16> {
+    200..=299
 }
 
 impl Default for ETag {
@@ -121,14 +223,26 @@ where
         ok(ETagMiddleware {
             service: Rc::new(service),
             strength: self.strength,
+            hasher: self.hasher,
+            max_body_size: self.max_body_size,
+            methods: self.methods.clone(),
+            statuses: self.statuses.clone(),
+            compute_etag: self.compute_etag.clone(),
         })
     }
 }
 
-/// Internal service wrapper that materializes response bodies before hashing.
+/// Internal service wrapper that materializes response bodies before hashing,
+/// unless the body is too large (or unbounded) to buffer safely, or the
+/// request/response falls outside the configured methods/statuses.
 pub struct ETagMiddleware<S> {
     service: Rc<S>,
     strength: Strength,
+    hasher: HashAlgorithm,
+    max_body_size: Option<usize>,
+    methods: Vec<Method>,
+    statuses: RangeInclusive<u16>,
+    compute_etag: Option<EtagHook>,
 }
 
 impl<S, B> Service<ServiceRequest> for ETagMiddleware<S>
@@ -146,16 +260,51 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let srv = Rc::clone(&self.service);
         let strength = self.strength;
+        let hasher = self.hasher;
+        let max_body_size = self.max_body_size;
+        let methods = self.methods.clone();
+        let statuses = self.statuses.clone();
+        let compute_etag = self.compute_etag.clone();
 
         Box::pin(async move {
             let res = srv.call(req).await?;
             let (req, res) = res.into_parts();
             let (mut head, body) = res.into_parts();
+
+            let in_scope =
+                methods.contains(req.method()) && statuses.contains(&head.status().as_u16());
+
+            if !in_scope {
+                let response = head.set_body(body).map_body(|_, body| body.boxed());
+                return Ok(ServiceResponse::new(req, response));
+            }
+
+            if let Some(limit) = max_body_size {
+                if !body_within_limit(&body, limit) {
+                    let response = head.set_body(body).map_body(|_, body| body.boxed());
+                    return Ok(ServiceResponse::new(req, response));
+                }
+            }
+
             let body_bytes = to_bytes(body).await.map_err(Into::into)?;
 
-            let etag_value = extract_or_compute_etag(&mut head, &body_bytes, strength);
+            let etag_value = extract_or_compute_etag(
+                &req,
+                &mut head,
+                &body_bytes,
+                strength,
+                hasher,
+                compute_etag.as_ref(),
+            );
+            let last_modified = head
+                .headers()
+                .get(header::LAST_MODIFIED)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
 
-            if let Some(precondition) = evaluate_conditionals(&req, &etag_value) {
+            if let Some(precondition) =
+                evaluate_conditionals(&req, &etag_value, last_modified.as_deref())
+            {
                 return Ok(ServiceResponse::new(req, precondition));
             }
 
@@ -166,10 +315,20 @@ where
     }
 }
 
+/// Returns `true` only when `body`'s size is known up front and does not
+/// exceed `limit`. Streamed bodies of unknown length are treated as over the
+/// limit so they are never buffered into memory.
+fn body_within_limit<B: MessageBody>(body: &B, limit: usize) -> bool {
+    matches!(body.size(), BodySize::Sized(size) if size as usize <= limit)
+}
+
 fn extract_or_compute_etag(
+    req: &actix_web::HttpRequest,
     head: &mut HttpResponse<()>,
     body: &Bytes,
     strength: Strength,
+    hasher: HashAlgorithm,
+    compute_etag: Option<&EtagHook>,
 ) -> String {
     if let Some(value) = head
         .headers()
@@ -179,7 +338,10 @@ fn extract_or_compute_etag(
         return value.trim().to_string();
     }
 
-    let value = build_entity_tag(body, strength);
+    let value = compute_etag
+        .and_then(|hook| hook(req, head, body))
+        .and_then(|value| format_etag_value(value, strength))
+        .unwrap_or_else(|| build_entity_tag(body, strength, hasher));
 
     if let Ok(header_value) = header::HeaderValue::from_str(&value) {
         head.headers_mut().insert(header::ETAG, header_value);
@@ -188,9 +350,53 @@ fn extract_or_compute_etag(
     value
 }
 
-/// Applies `If-Match`/`If-None-Match` rules and returns a short-circuit response when
-/// the request preconditions resolve without reaching the wrapped service.
-fn evaluate_conditionals(req: &actix_web::HttpRequest, etag: &str) -> Option<HttpResponse> {
+/// Wraps a raw validator value from [`ETag::compute_etag_with`] into a proper
+/// entity-tag, unless it already looks like one (quoted, optionally
+/// `W/`-prefixed). Returns `None` when the value contains characters the
+/// entity-tag grammar (RFC 7232 §2.3 `etagc`) disallows, e.g. an embedded
+/// unescaped `"` or a control character, so callers can fall back to a
+/// hash-based tag instead of handing a malformed string to header insertion.
+fn format_etag_value(value: String, strength: Strength) -> Option<String> {
+    if value.starts_with('"') || value.starts_with("W/\"") {
+        return is_valid_entity_tag(&value).then_some(value);
+    }
+
+    if !value.bytes().all(is_etagc) {
+        return None;
+    }
+
+    Some(match strength {
+        Strength::Strong => format!("\"{value}\""),
+        Strength::Weak => format!("W/\"{value}\""),
+    })
+}
+
+/// Validates a fully-formed entity-tag: optional `W/` prefix, a single pair
+/// of surrounding quotes, and `etagc`-only content in between.
+fn is_valid_entity_tag(value: &str) -> bool {
+    let quoted = value.strip_prefix("W/").unwrap_or(value);
+
+    quoted.len() >= 2
+        && quoted.starts_with('"')
+        && quoted.ends_with('"')
+        && quoted[1..quoted.len() - 1].bytes().all(is_etagc)
+}
+
+/// `etagc` per RFC 7232 §2.3: `%x21 / %x23-7E / obs-text`, i.e. any visible
+/// ASCII character except `"`, or non-ASCII (obs-text) bytes.
+fn is_etagc(byte: u8) -> bool {
+    byte == 0x21 || (0x23..=0x7e).contains(&byte) || byte >= 0x80
+}
+
+/// Applies `If-Match`/`If-None-Match` rules, falling back to `If-Modified-Since`
+/// when the request carries no `If-None-Match`, and returns a short-circuit
+/// response that replaces the wrapped service's response when the request
+/// preconditions resolve.
+fn evaluate_conditionals(
+    req: &actix_web::HttpRequest,
+    etag: &str,
+    last_modified: Option<&str>,
+) -> Option<HttpResponse> {
     if let Some(if_match) = req
         .headers()
         .get(header::IF_MATCH)
@@ -222,11 +428,59 @@ fn evaluate_conditionals(req: &actix_web::HttpRequest, etag: &str) -> Option<Htt
                     .finish(),
             );
         }
+
+        return None;
+    }
+
+    if matches!(*req.method(), Method::GET | Method::HEAD) && not_modified_since(req, last_modified)
+    {
+        return Some(
+            HttpResponse::build(StatusCode::NOT_MODIFIED)
+                .insert_header((header::ETAG, etag.to_string()))
+                .finish(),
+        );
     }
 
     None
 }
 
+/// Returns `true` when the request's `If-Modified-Since` timestamp is not
+/// older than the response's `Last-Modified` timestamp, per RFC 7232 §3.3.
+/// Only consulted when the request has no `If-None-Match`.
+fn not_modified_since(req: &actix_web::HttpRequest, last_modified: Option<&str>) -> bool {
+    let if_modified_since = req
+        .headers()
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|h| h.to_str().ok());
+
+    let (Some(if_modified_since), Some(last_modified)) = (if_modified_since, last_modified) else {
+        return false;
+    };
+
+    let (Some(client_time), Some(resource_time)) = (
+        parse_http_date(if_modified_since),
+        parse_http_date(last_modified),
+    ) else {
+        return false;
+    };
+
+    truncate_to_secs(resource_time) <= truncate_to_secs(client_time)
+}
+
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    value
+        .trim()
+        .parse::<header::HttpDate>()
+        .ok()
+        .map(SystemTime::from)
+}
+
+fn truncate_to_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 fn match_if_match(etag: &str, header_value: &str) -> bool {
     header_value
         .split(',')
@@ -249,10 +503,8 @@ fn match_if_none_match(etag: &str, header_value: &str) -> bool {
         })
 }
 
-fn build_entity_tag(body: &Bytes, strength: Strength) -> String {
-    let mut hasher = Hasher::new();
-    hasher.update(body);
-    let digest = format!("{:x}", hasher.finalize());
+fn build_entity_tag(body: &Bytes, strength: Strength, hasher: HashAlgorithm) -> String {
+    let digest = digest_body(body, hasher);
 
     match strength {
         Strength::Strong => format!("\"{}\"", digest),
@@ -260,6 +512,27 @@ fn build_entity_tag(body: &Bytes, strength: Strength) -> String {
     }
 }
 
+/// Hashes `body` with the selected algorithm. `Xxh3`/`Blake3` digests are
+/// returned as URL-safe, unpadded base64 to keep the resulting ETag compact;
+/// `Crc32` stays hex-encoded to preserve its historical output for backward
+/// compatibility.
+fn digest_body(body: &Bytes, hasher: HashAlgorithm) -> String {
+    match hasher {
+        // Hex-encoded to preserve the historical CRC32 output byte-for-byte;
+        // upgrading to a new algorithm is opt-in, not a silent cache-buster.
+        HashAlgorithm::Crc32 => {
+            let mut crc = Crc32Hasher::new();
+            crc.update(body);
+            format!("{:x}", crc.finalize())
+        }
+        HashAlgorithm::Xxh3 => {
+            let digest = xxhash_rust::xxh3::xxh3_128(body);
+            URL_SAFE_NO_PAD.encode(digest.to_be_bytes())
+        }
+        HashAlgorithm::Blake3 => URL_SAFE_NO_PAD.encode(blake3::hash(body).as_bytes()),
+    }
+}
+
 fn strong_compare(left: &str, right: &str) -> bool {
     !is_weak(left) && !is_weak(right) && left == right
 }
@@ -285,7 +558,7 @@ mod tests {
 
     fn expected_etag(payload: &[u8], strength: Strength) -> String {
         let bytes = Bytes::copy_from_slice(payload);
-        build_entity_tag(&bytes, strength)
+        build_entity_tag(&bytes, strength, HashAlgorithm::Crc32)
     }
 
     #[actix_web::test]
@@ -446,4 +719,242 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
     }
+
+    #[actix_web::test]
+    async fn uses_configured_hash_algorithm() {
+        let app = init_service(
+            App::new()
+                .wrap(ETag::strong().with_hasher(HashAlgorithm::Xxh3))
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+                ),
+        )
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = Bytes::from_static(b"hello");
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ETAG)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            build_entity_tag(&bytes, Strength::Strong, HashAlgorithm::Xxh3)
+        );
+    }
+
+    #[actix_web::test]
+    async fn returns_not_modified_for_if_modified_since_when_not_newer() {
+        let app = init_service(App::new().wrap(ETag::strong()).route(
+            "/",
+            web::get().to(|| async {
+                HttpResponse::Ok()
+                    .insert_header((header::LAST_MODIFIED, "Wed, 21 Oct 2015 07:28:00 GMT"))
+                    .body("hello")
+            }),
+        ))
+        .await;
+
+        let request = TestRequest::get()
+            .uri("/")
+            .insert_header((header::IF_MODIFIED_SINCE, "Wed, 21 Oct 2015 07:28:00 GMT"))
+            .to_request();
+        let response: ServiceResponse = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_web::test]
+    async fn if_none_match_takes_precedence_over_if_modified_since() {
+        let app = init_service(App::new().wrap(ETag::strong()).route(
+            "/",
+            web::get().to(|| async {
+                HttpResponse::Ok()
+                    .insert_header((header::LAST_MODIFIED, "Wed, 21 Oct 2015 07:28:00 GMT"))
+                    .body("hello")
+            }),
+        ))
+        .await;
+
+        let request = TestRequest::get()
+            .uri("/")
+            .insert_header((header::IF_NONE_MATCH, "\"stale-tag\""))
+            .insert_header((header::IF_MODIFIED_SINCE, "Wed, 21 Oct 2015 07:28:00 GMT"))
+            .to_request();
+        let response: ServiceResponse = call_service(&app, request).await;
+
+        // The etag does not match, so If-None-Match wins and the request proceeds,
+        // even though If-Modified-Since alone would have short-circuited to 304.
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn skips_etag_for_bodies_over_the_configured_limit() {
+        let app = init_service(App::new().wrap(ETag::strong().max_body_size(4)).route(
+            "/",
+            web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+        ))
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::ETAG));
+    }
+
+    #[actix_web::test]
+    async fn computes_etag_for_bodies_within_the_configured_limit() {
+        let app = init_service(App::new().wrap(ETag::strong().max_body_size(1024)).route(
+            "/",
+            web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+        ))
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::ETAG));
+    }
+
+    #[actix_web::test]
+    async fn skips_etag_for_methods_outside_the_configured_set() {
+        let app = init_service(App::new().wrap(ETag::strong()).route(
+            "/",
+            web::post().to(|| async { HttpResponse::Ok().body("hello") }),
+        ))
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::post().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::ETAG));
+    }
+
+    #[actix_web::test]
+    async fn skips_etag_for_statuses_outside_the_configured_range() {
+        let app = init_service(App::new().wrap(ETag::strong()).route(
+            "/",
+            web::get().to(|| async { HttpResponse::InternalServerError().body("boom") }),
+        ))
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!response.headers().contains_key(header::ETAG));
+    }
+
+    #[actix_web::test]
+    async fn honours_widened_method_and_status_configuration() {
+        let app = init_service(
+            App::new()
+                .wrap(ETag::strong().methods([Method::POST]).statuses(200..=599))
+                .route(
+                    "/",
+                    web::post().to(|| async { HttpResponse::InternalServerError().body("boom") }),
+                ),
+        )
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::post().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().contains_key(header::ETAG));
+    }
+
+    #[actix_web::test]
+    async fn uses_hook_supplied_etag_without_hashing_body() {
+        let app = init_service(
+            App::new()
+                .wrap(
+                    ETag::strong()
+                        .compute_etag_with(|_req, _res, _body| Some("row-version-42".to_string())),
+                )
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+                ),
+        )
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ETAG)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "\"row-version-42\""
+        );
+    }
+
+    #[actix_web::test]
+    async fn falls_back_to_hashing_when_hook_returns_none() {
+        let app = init_service(
+            App::new()
+                .wrap(ETag::strong().compute_etag_with(|_req, _res, _body| None))
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+                ),
+        )
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ETAG)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            expected_etag(b"hello", Strength::Strong)
+        );
+    }
+
+    #[actix_web::test]
+    async fn falls_back_to_hashing_when_hook_returns_invalid_etagc() {
+        let app = init_service(
+            App::new()
+                .wrap(ETag::strong().compute_etag_with(|_req, _res, _body| {
+                    Some("embedded \" quote".to_string())
+                }))
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().body("hello") }),
+                ),
+        )
+        .await;
+
+        let response: ServiceResponse =
+            call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ETAG)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            expected_etag(b"hello", Strength::Strong)
+        );
+    }
 }